@@ -0,0 +1,114 @@
+use crate::helpers::Side;
+
+/// A closed OHLCV bar covering `[start_ts, end_ts)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub buy_volume: f64,
+    pub start_ts: u64,
+    pub end_ts: u64,
+}
+
+/// Aggregates a stream of `(time_ns, price, amount, Side)` trade prints into
+/// fixed-`interval_ns` OHLCV candles, emitting a closed `Candle` each time a
+/// bucket boundary is crossed.
+pub struct CandleBuilder {
+    interval_ns: u64,
+    current: Option<Candle>,
+}
+
+impl CandleBuilder {
+    pub fn new(interval_ns: u64) -> CandleBuilder {
+        CandleBuilder {
+            interval_ns,
+            current: None,
+        }
+    }
+
+    fn bucket_start(&self, ts: u64) -> u64 {
+        (ts / self.interval_ns) * self.interval_ns
+    }
+
+    /// Feeds one trade print into the builder. Returns the just-closed candle
+    /// when `ts` crosses into a new bucket, otherwise `None`.
+    pub fn push(&mut self, ts: u64, price: f64, amount: f64, side: Side) -> Option<Candle> {
+        let new_bucket = self.bucket_start(ts);
+
+        let closed = match &mut self.current {
+            Some(bar) if bar.start_ts / self.interval_ns != new_bucket / self.interval_ns => {
+                self.current.take()
+            }
+            _ => None,
+        };
+
+        let bar = self.current.get_or_insert(Candle {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 0.0,
+            buy_volume: 0.0,
+            start_ts: new_bucket,
+            end_ts: new_bucket + self.interval_ns,
+        });
+
+        bar.high = bar.high.max(price);
+        bar.low = bar.low.min(price);
+        bar.close = price;
+        bar.volume += amount;
+        if side == Side::Buy {
+            bar.buy_volume += amount;
+        }
+
+        closed
+    }
+
+    /// Closes and returns the in-progress bar, if any, without waiting for the
+    /// next bucket boundary. Useful when shutting down mid-bucket.
+    pub fn flush(&mut self) -> Option<Candle> {
+        self.current.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candle_builder_aggregates_within_bucket() {
+        let mut builder = CandleBuilder::new(1_000);
+
+        assert_eq!(builder.push(100, 10.0, 1.0, Side::Buy), None);
+        assert_eq!(builder.push(500, 12.0, 2.0, Side::Sell), None);
+        assert_eq!(builder.push(900, 9.0, 1.0, Side::Buy), None);
+
+        let bar = builder.flush().expect("expected an in-progress bar");
+        assert_eq!(bar.open, 10.0);
+        assert_eq!(bar.high, 12.0);
+        assert_eq!(bar.low, 9.0);
+        assert_eq!(bar.close, 9.0);
+        assert_eq!(bar.volume, 4.0);
+        assert_eq!(bar.buy_volume, 2.0);
+    }
+
+    #[test]
+    fn test_candle_builder_closes_on_boundary_cross() {
+        let mut builder = CandleBuilder::new(1_000);
+
+        assert_eq!(builder.push(100, 10.0, 1.0, Side::Buy), None);
+        let closed = builder
+            .push(1_100, 20.0, 1.0, Side::Buy)
+            .expect("expected the first bucket to close");
+
+        assert_eq!(closed.start_ts, 0);
+        assert_eq!(closed.close, 10.0);
+
+        let in_progress = builder.flush().expect("expected second bucket");
+        assert_eq!(in_progress.start_ts, 1_000);
+        assert_eq!(in_progress.open, 20.0);
+    }
+}
@@ -0,0 +1,439 @@
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read as IoRead, Write};
+use std::path::Path;
+
+use chrono::Utc;
+use csv::Reader;
+use flate2::write::GzEncoder;
+use flate2::Compression as GzCompression;
+use memmap2::Mmap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    None,
+    Buy,
+    Sell,
+}
+
+/// How a completed CSV log segment should be archived once it is rotated out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zip,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsFile {
+    pub market_name: String,
+    pub time_delta: u64,
+    pub bb_period: usize,
+    pub bb_std_dev: f64,
+    pub orderbook_depth: u32,
+    pub live: bool,
+    pub order_size: Decimal,
+    pub tp_percent: Decimal,
+    pub sl_percent: Decimal,
+    pub write_to_file: bool,
+    pub compression: Compression,
+    pub max_log_bytes: u64,
+    pub log_candles: bool,
+}
+
+/// Loads a `SettingsFile` from a JSON file on disk, panicking on malformed input
+/// since a bad settings file means the bot has nothing sensible to trade with.
+pub fn read_settings(filename: &str) -> SettingsFile {
+    let file = File::open(filename).expect("Failed to open settings file");
+    serde_json::from_reader(file).expect("Failed to parse settings file")
+}
+
+pub fn invert_side(side: Side) -> Side {
+    match side {
+        Side::Buy => Side::Sell,
+        Side::Sell => Side::Buy,
+        Side::None => Side::None,
+    }
+}
+
+/// Converts a price/amount increment (e.g. `0.1`) into the number of decimal
+/// places it represents, so order sizes can be rounded to the exchange's tick size.
+pub fn convert_increment_to_precision(increment: Decimal) -> u32 {
+    increment.normalize().scale()
+}
+
+/// Appends a single fill to `filename` as a human-readable CSV row, creating
+/// the file with no header if it doesn't exist yet.
+pub fn write_to_csv(
+    filename: &str,
+    price: Decimal,
+    amount: Decimal,
+    side: &Side,
+    count: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filename)?;
+
+    writeln!(file, "{},{},{},{:?}", count, price, amount, side)?;
+    Ok(())
+}
+
+/// Appends a single closed `Candle` to `filename` as a CSV row, for callers
+/// that want per-bucket OHLCV bars instead of one row per fill.
+pub fn write_candle_to_csv(filename: &str, candle: &crate::candles::Candle) -> Result<(), Box<dyn Error>> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filename)?;
+
+    writeln!(
+        file,
+        "{},{},{},{},{},{},{},{}",
+        candle.start_ts,
+        candle.end_ts,
+        candle.open,
+        candle.high,
+        candle.low,
+        candle.close,
+        candle.volume,
+        candle.buy_volume,
+    )?;
+    Ok(())
+}
+
+/// Appends one order lifecycle transition to `filename` as a CSV row,
+/// including the resulting `OrderState`/`OrderEvent` so the log can
+/// reconstruct an in-flight order's state after a restart — unlike
+/// `write_to_csv`'s fill schema, which has no field for state at all.
+pub fn write_order_state_to_csv(
+    filename: &str,
+    market_name: &str,
+    side: &Side,
+    price: Decimal,
+    event: crate::order_state::OrderEvent,
+    state: crate::order_state::OrderState,
+    count: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filename)?;
+
+    writeln!(
+        file,
+        "{},{},{:?},{},{:?},{:?}",
+        count, market_name, side, price, event, state
+    )?;
+    Ok(())
+}
+
+pub const SERIALIZED_SIZE: usize = 32;
+
+/// Exchange codes understood by the binary trade log. New exchanges must be
+/// appended at the end so previously written logs keep decoding correctly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeCode {
+    Unknown,
+    Binance,
+    Coinbase,
+    Kraken,
+    Ftx,
+}
+
+impl From<ExchangeCode> for u8 {
+    fn from(code: ExchangeCode) -> u8 {
+        match code {
+            ExchangeCode::Unknown => 0,
+            ExchangeCode::Binance => 1,
+            ExchangeCode::Coinbase => 2,
+            ExchangeCode::Kraken => 3,
+            ExchangeCode::Ftx => 4,
+        }
+    }
+}
+
+impl From<u8> for ExchangeCode {
+    fn from(byte: u8) -> ExchangeCode {
+        match byte {
+            1 => ExchangeCode::Binance,
+            2 => ExchangeCode::Coinbase,
+            3 => ExchangeCode::Kraken,
+            4 => ExchangeCode::Ftx,
+            _ => ExchangeCode::Unknown,
+        }
+    }
+}
+
+/// Currency codes understood by the binary trade log, used for both the base
+/// and quote legs of a market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurrencyCode {
+    Unknown,
+    Usd,
+    Usdt,
+    Btc,
+    Eth,
+}
+
+impl From<CurrencyCode> for u8 {
+    fn from(code: CurrencyCode) -> u8 {
+        match code {
+            CurrencyCode::Unknown => 0,
+            CurrencyCode::Usd => 1,
+            CurrencyCode::Usdt => 2,
+            CurrencyCode::Btc => 3,
+            CurrencyCode::Eth => 4,
+        }
+    }
+}
+
+impl From<u8> for CurrencyCode {
+    fn from(byte: u8) -> CurrencyCode {
+        match byte {
+            1 => CurrencyCode::Usd,
+            2 => CurrencyCode::Usdt,
+            3 => CurrencyCode::Btc,
+            4 => CurrencyCode::Eth,
+            _ => CurrencyCode::Unknown,
+        }
+    }
+}
+
+impl From<Side> for u8 {
+    fn from(side: Side) -> u8 {
+        match side {
+            Side::None => 0,
+            Side::Buy => 1,
+            Side::Sell => 2,
+        }
+    }
+}
+
+impl From<u8> for Side {
+    fn from(byte: u8) -> Side {
+        match byte {
+            1 => Side::Buy,
+            2 => Side::Sell,
+            _ => Side::None,
+        }
+    }
+}
+
+/// A single fill as stored in the fixed-width binary trade log.
+///
+/// `server_time` is the exchange-reported timestamp when present; it is kept
+/// as an offset from `time` so it packs into a `u32` instead of a second `u64`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeRecord {
+    pub exchange: ExchangeCode,
+    pub base: CurrencyCode,
+    pub quote: CurrencyCode,
+    pub side: Side,
+    pub server_time: Option<u32>,
+    pub time: u64,
+    pub price: f64,
+    pub amount: f64,
+}
+
+impl TradeRecord {
+    fn to_bytes(self) -> [u8; SERIALIZED_SIZE] {
+        let mut buf = [0u8; SERIALIZED_SIZE];
+        buf[0] = self.exchange.into();
+        buf[1] = self.base.into();
+        buf[2] = self.quote.into();
+        buf[3] = self.side.into();
+        buf[4..8].copy_from_slice(&self.server_time.unwrap_or(0).to_le_bytes());
+        buf[8..16].copy_from_slice(&self.time.to_le_bytes());
+        buf[16..24].copy_from_slice(&self.price.to_le_bytes());
+        buf[24..32].copy_from_slice(&self.amount.to_le_bytes());
+        buf
+    }
+
+    fn from_bytes(bytes: &[u8]) -> TradeRecord {
+        debug_assert_eq!(bytes.len(), SERIALIZED_SIZE);
+        let server_time = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        TradeRecord {
+            exchange: bytes[0].into(),
+            base: bytes[1].into(),
+            quote: bytes[2].into(),
+            side: bytes[3].into(),
+            server_time: if server_time == 0 { None } else { Some(server_time) },
+            time: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            price: f64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            amount: f64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+        }
+    }
+
+    /// Re-rounds `price` to the given tick increment, mirroring the precision
+    /// handling `write_to_csv` callers apply before logging a fill.
+    pub fn rounded_price(&self, increment: Decimal) -> Decimal {
+        let precision = convert_increment_to_precision(increment);
+        Decimal::from_f64_retain(self.price)
+            .unwrap_or_default()
+            .round_dp(precision)
+    }
+}
+
+/// Appends a single fill to `filename` as a packed `SERIALIZED_SIZE`-byte row.
+pub fn write_to_binary(filename: &str, record: TradeRecord) -> Result<(), Box<dyn Error>> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filename)?;
+
+    file.write_all(&record.to_bytes())?;
+    Ok(())
+}
+
+/// Memory-maps `filename` and returns an iterator over the `TradeRecord`s it
+/// contains, decoding each fixed-width row lazily as the iterator advances.
+pub fn read_binary(filename: impl AsRef<Path>) -> Result<BinaryTradeIter, Box<dyn Error>> {
+    let file = File::open(filename)?;
+    let mmap = unsafe { Mmap::map(&file)? };
+    Ok(BinaryTradeIter { mmap, offset: 0 })
+}
+
+pub struct BinaryTradeIter {
+    mmap: Mmap,
+    offset: usize,
+}
+
+impl Iterator for BinaryTradeIter {
+    type Item = TradeRecord;
+
+    fn next(&mut self) -> Option<TradeRecord> {
+        if self.offset + SERIALIZED_SIZE > self.mmap.len() {
+            return None;
+        }
+        let record = TradeRecord::from_bytes(&self.mmap[self.offset..self.offset + SERIALIZED_SIZE]);
+        self.offset += SERIALIZED_SIZE;
+        Some(record)
+    }
+}
+
+/// A `write_to_csv` sink that rotates the underlying file once it exceeds
+/// `max_log_bytes` or the UTC date rolls over, archiving the completed
+/// segment according to `compression` before opening a fresh file.
+pub struct RotatingCsvWriter {
+    base_filename: String,
+    compression: Compression,
+    max_log_bytes: u64,
+    bytes_written: u64,
+    current_date: chrono::NaiveDate,
+    segment_index: u64,
+}
+
+impl RotatingCsvWriter {
+    pub fn new(base_filename: &str, compression: Compression, max_log_bytes: u64) -> RotatingCsvWriter {
+        let bytes_written = fs::metadata(base_filename).map(|m| m.len()).unwrap_or(0);
+        RotatingCsvWriter {
+            base_filename: base_filename.to_string(),
+            compression,
+            max_log_bytes,
+            bytes_written,
+            current_date: Utc::now().date_naive(),
+            segment_index: 0,
+        }
+    }
+
+    /// Writes one fill, rotating the active file first if it has grown past
+    /// `max_log_bytes` or the UTC date has rolled over since the last write.
+    pub fn write(
+        &mut self,
+        price: Decimal,
+        amount: Decimal,
+        side: &Side,
+        count: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        let today = Utc::now().date_naive();
+        let size_exceeded = self.max_log_bytes > 0 && self.bytes_written > self.max_log_bytes;
+
+        if (size_exceeded || today != self.current_date) && self.bytes_written > 0 {
+            self.rotate()?;
+        }
+        self.current_date = today;
+
+        write_to_csv(&self.base_filename, price, amount, side, count)?;
+        self.bytes_written = fs::metadata(&self.base_filename).map(|m| m.len()).unwrap_or(0);
+
+        Ok(())
+    }
+
+    /// Closes out the active segment, giving it a unique name so successive
+    /// rotations in a long-running session never overwrite one another, then
+    /// leaves a fresh, empty file at `base_filename` for subsequent writes.
+    fn rotate(&mut self) -> Result<(), Box<dyn Error>> {
+        self.segment_index += 1;
+
+        match self.compression {
+            Compression::None => {
+                let archived = format!("{}.{}.csv", self.base_filename, self.segment_index);
+                fs::rename(&self.base_filename, archived)?;
+            }
+            Compression::Gzip => {
+                let archived = format!("{}.{}.gz", self.base_filename, self.segment_index);
+                compress_to_gzip(&self.base_filename, &archived)?;
+            }
+            Compression::Zip => {
+                let archived = format!("{}.{}.zip", self.base_filename, self.segment_index);
+                compress_to_zip(&self.base_filename, &archived)?;
+            }
+        }
+        self.bytes_written = 0;
+        Ok(())
+    }
+}
+
+/// Compresses `filename` into `archive_path` with DEFLATE and removes the
+/// uncompressed segment.
+pub fn compress_to_gzip(filename: &str, archive_path: &str) -> Result<(), Box<dyn Error>> {
+    let mut input = File::open(filename)?;
+    let output = File::create(archive_path)?;
+    let mut encoder = GzEncoder::new(output, GzCompression::default());
+
+    std::io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?;
+    fs::remove_file(filename)?;
+    Ok(())
+}
+
+/// Compresses `filename` into a single-entry `archive_path` zip with DEFLATE
+/// and removes the uncompressed segment.
+pub fn compress_to_zip(filename: &str, archive_path: &str) -> Result<(), Box<dyn Error>> {
+    let input = fs::read(filename)?;
+    let output = File::create(archive_path)?;
+    let mut writer = zip::ZipWriter::new(output);
+    let entry_name = Path::new(filename)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(filename);
+
+    writer.start_file(entry_name, zip::write::FileOptions::default())?;
+    writer.write_all(&input)?;
+    writer.finish()?;
+    fs::remove_file(filename)?;
+    Ok(())
+}
+
+/// Reads a trade-record CSV, transparently decompressing `.gz` and `.zip`
+/// archives produced by `RotatingCsvWriter` so backtests can replay across
+/// rotated segments without manually unzipping them first.
+pub fn read_compressed_csv(filename: &str) -> Result<Reader<Box<dyn IoRead>>, Box<dyn Error>> {
+    let reader: Box<dyn IoRead> = if filename.ends_with(".gz") {
+        Box::new(flate2::read::GzDecoder::new(File::open(filename)?))
+    } else if filename.ends_with(".zip") {
+        let mut archive = zip::ZipArchive::new(File::open(filename)?)?;
+        let mut contents = Vec::new();
+        archive.by_index(0)?.read_to_end(&mut contents)?;
+        Box::new(std::io::Cursor::new(contents))
+    } else {
+        Box::new(File::open(filename)?)
+    };
+
+    // `write_to_csv` writes no header row, so every row is a fill record.
+    Ok(csv::ReaderBuilder::new().has_headers(false).from_reader(reader))
+}
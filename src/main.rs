@@ -0,0 +1,79 @@
+mod candles;
+mod helpers;
+mod order_state;
+mod pricing;
+mod windows;
+
+#[cfg(test)]
+mod tests;
+
+use candles::CandleBuilder;
+use helpers::{read_settings, write_candle_to_csv, RotatingCsvWriter, Side, SettingsFile};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use windows::WeightedMeanWindow;
+
+/// Computes the Bollinger Bands (lower, middle, upper) for the most recent
+/// `period` closes, using `std_dev` standard deviations for the bands.
+///
+/// `center` overrides the band's middle line (e.g. a VWAP or time-weighted
+/// mid-price from a `WeightedMeanWindow`) instead of the plain period mean;
+/// pass `None` to fall back to that simple mean.
+fn bollinger_bands(closes: &[f64], period: usize, std_dev: f64, center: Option<f64>) -> Option<(f64, f64, f64)> {
+    if closes.len() < period {
+        return None;
+    }
+
+    let window = &closes[closes.len() - period..];
+    let mean = center.unwrap_or_else(|| window.iter().sum::<f64>() / period as f64);
+    let variance = window.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / period as f64;
+    let std = variance.sqrt();
+
+    Some((mean - std_dev * std, mean, mean + std_dev * std))
+}
+
+fn main() {
+    let settings: SettingsFile = read_settings("settings.json");
+
+    let mut builder = CandleBuilder::new(settings.time_delta * 1_000_000_000);
+    let mut closes: Vec<f64> = Vec::new();
+    let mut vwap = WeightedMeanWindow::new(settings.time_delta * 1_000_000_000);
+    let mut log_writer = RotatingCsvWriter::new("trades.csv", settings.compression, settings.max_log_bytes);
+
+    // Placeholder for the live trade-print feed; each print advances the
+    // candle builder and VWAP window, and once a bar closes, the Bollinger
+    // Bands are centered on the running VWAP instead of the plain period mean.
+    let prints: Vec<(u64, f64, f64, Side)> = Vec::new();
+    for (count, (time_ns, price, amount, side)) in prints.into_iter().enumerate() {
+        vwap.push(
+            time_ns,
+            Decimal::from_f64(price).unwrap_or_default(),
+            Decimal::from_f64(amount).unwrap_or_default(),
+        );
+
+        // `log_candles` picks the granularity written to disk: per-fill rows
+        // via `log_writer`, or one row per closed candle via
+        // `write_candle_to_csv` below — never both.
+        if settings.write_to_file && !settings.log_candles {
+            log_writer
+                .write(
+                    Decimal::from_f64(price).unwrap_or_default(),
+                    Decimal::from_f64(amount).unwrap_or_default(),
+                    &side,
+                    count,
+                )
+                .expect("Failed to write trade to log");
+        }
+
+        if let Some(candle) = builder.push(time_ns, price, amount, side) {
+            closes.push(candle.close);
+
+            let vwap_center = vwap.mean().and_then(|m| m.to_f64());
+            bollinger_bands(&closes, settings.bb_period, settings.bb_std_dev, vwap_center);
+
+            if settings.write_to_file && settings.log_candles {
+                write_candle_to_csv("candles.csv", &candle).expect("Failed to write candle to log");
+            }
+        }
+    }
+}
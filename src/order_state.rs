@@ -0,0 +1,196 @@
+use rust_decimal::Decimal;
+
+use crate::helpers::{write_order_state_to_csv, Side, SettingsFile};
+
+/// The lifecycle of a single managed order, from entry through to its TP/SL
+/// resolution. Replaces the ad-hoc "taken" boolean, which loses information
+/// about where in the lifecycle an order was if the bot restarts mid-flight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderState {
+    Open,
+    Filled,
+    TpPlaced,
+    SlPlaced,
+    Closed,
+    Cancelled,
+    Failed,
+}
+
+/// An event that can move an order from one `OrderState` to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderEvent {
+    Fill,
+    PlaceTp,
+    PlaceSl,
+    Close,
+    Cancel,
+    Fail,
+}
+
+/// An error returned by `ManagedOrder::transition` when the requested event
+/// does not have a legal move out of the current state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalTransition {
+    pub from: OrderState,
+    pub event: OrderEvent,
+}
+
+/// A single order the bot is tracking end-to-end, including the TP/SL
+/// percentages it was opened with so those levels survive a restart.
+#[derive(Debug, Clone)]
+pub struct ManagedOrder {
+    pub market_name: String,
+    pub entry_side: Side,
+    pub entry_price: Decimal,
+    pub tp_percent: Decimal,
+    pub sl_percent: Decimal,
+    pub state: OrderState,
+}
+
+impl ManagedOrder {
+    pub fn new(market_name: String, entry_side: Side, entry_price: Decimal, settings: &SettingsFile) -> ManagedOrder {
+        ManagedOrder {
+            market_name,
+            entry_side,
+            entry_price,
+            tp_percent: settings.tp_percent,
+            sl_percent: settings.sl_percent,
+            state: OrderState::Open,
+        }
+    }
+
+    /// Attempts to apply `event`, moving `self.state` forward if the move is
+    /// legal and returning an `IllegalTransition` otherwise (e.g. `Open ->
+    /// Closed` without first passing through `Filled`).
+    pub fn transition(&mut self, event: OrderEvent) -> Result<OrderState, IllegalTransition> {
+        let next = match (self.state, event) {
+            (OrderState::Open, OrderEvent::Fill) => OrderState::Filled,
+            (OrderState::Open, OrderEvent::Cancel) => OrderState::Cancelled,
+            (OrderState::Open, OrderEvent::Fail) => OrderState::Failed,
+
+            (OrderState::Filled, OrderEvent::PlaceTp) => OrderState::TpPlaced,
+            (OrderState::Filled, OrderEvent::PlaceSl) => OrderState::SlPlaced,
+            (OrderState::Filled, OrderEvent::Close) => OrderState::Closed,
+            (OrderState::Filled, OrderEvent::Fail) => OrderState::Failed,
+
+            (OrderState::TpPlaced, OrderEvent::Close) => OrderState::Closed,
+            (OrderState::TpPlaced, OrderEvent::Cancel) => OrderState::Cancelled,
+            (OrderState::TpPlaced, OrderEvent::Fail) => OrderState::Failed,
+
+            (OrderState::SlPlaced, OrderEvent::Close) => OrderState::Closed,
+            (OrderState::SlPlaced, OrderEvent::Cancel) => OrderState::Cancelled,
+            (OrderState::SlPlaced, OrderEvent::Fail) => OrderState::Failed,
+
+            _ => {
+                return Err(IllegalTransition {
+                    from: self.state,
+                    event,
+                })
+            }
+        };
+
+        self.state = next;
+        Ok(next)
+    }
+
+    /// Logs the event that just fired and the resulting state as a row in
+    /// `filename`, so state changes survive a crash and can be replayed on
+    /// restart instead of being indistinguishable from one another.
+    pub fn log_transition(
+        &self,
+        filename: &str,
+        event: OrderEvent,
+        count: usize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        write_order_state_to_csv(
+            filename,
+            &self.market_name,
+            &self.entry_side,
+            self.entry_price,
+            event,
+            self.state,
+            count,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::prelude::FromPrimitive;
+    use std::fs;
+
+    fn settings() -> SettingsFile {
+        SettingsFile {
+            market_name: "BTC-USD".to_string(),
+            time_delta: 1,
+            bb_period: 10,
+            bb_std_dev: 2.0,
+            orderbook_depth: 0,
+            live: false,
+            order_size: Decimal::from_f64(1.0).unwrap(),
+            tp_percent: Decimal::from_f64(0.02).unwrap(),
+            sl_percent: Decimal::from_f64(0.01).unwrap(),
+            write_to_file: false,
+            compression: crate::helpers::Compression::None,
+            max_log_bytes: 0,
+            log_candles: false,
+        }
+    }
+
+    #[test]
+    fn test_legal_transitions_through_tp() {
+        let mut order = ManagedOrder::new(
+            "BTC-USD".to_string(),
+            Side::Buy,
+            Decimal::from_f64(100.0).unwrap(),
+            &settings(),
+        );
+
+        assert_eq!(order.transition(OrderEvent::Fill), Ok(OrderState::Filled));
+        assert_eq!(order.transition(OrderEvent::PlaceTp), Ok(OrderState::TpPlaced));
+        assert_eq!(order.transition(OrderEvent::Close), Ok(OrderState::Closed));
+    }
+
+    #[test]
+    fn test_cannot_skip_filled_to_reach_closed() {
+        let mut order = ManagedOrder::new(
+            "BTC-USD".to_string(),
+            Side::Sell,
+            Decimal::from_f64(100.0).unwrap(),
+            &settings(),
+        );
+
+        let err = order.transition(OrderEvent::Close).unwrap_err();
+        assert_eq!(err.from, OrderState::Open);
+        assert_eq!(err.event, OrderEvent::Close);
+        assert_eq!(order.state, OrderState::Open);
+    }
+
+    #[test]
+    fn test_log_transition_records_distinct_rows_per_state() {
+        let filename = "test_log_transition.csv";
+        let mut order = ManagedOrder::new(
+            "BTC-USD".to_string(),
+            Side::Buy,
+            Decimal::from_f64(100.0).unwrap(),
+            &settings(),
+        );
+
+        order.transition(OrderEvent::Fill).unwrap();
+        order.log_transition(filename, OrderEvent::Fill, 1).unwrap();
+
+        order.transition(OrderEvent::PlaceTp).unwrap();
+        order.log_transition(filename, OrderEvent::PlaceTp, 2).unwrap();
+
+        let contents = fs::read_to_string(filename).expect("Failed to read log file");
+        let lines: Vec<&str> = contents.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("Fill") && lines[0].contains("Filled"));
+        assert!(lines[1].contains("PlaceTp") && lines[1].contains("TpPlaced"));
+        assert_ne!(lines[0], lines[1]);
+
+        fs::remove_file(filename).expect("Failed to remove test file");
+    }
+}
@@ -0,0 +1,262 @@
+use std::f64::consts::PI;
+use std::fs::File;
+
+use serde::{Deserialize, Serialize};
+
+/// Parameters for a single European or American option, loaded from a JSON
+/// blob by `read_option_params`, which follows the same serde path that
+/// `read_settings` uses for `SettingsFile`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionParams {
+    pub spot: f64,
+    pub strike: f64,
+    pub rate: f64,
+    pub volatility: f64,
+    pub time_to_expiry: f64,
+    pub is_call: bool,
+}
+
+/// Loads `OptionParams` from a JSON file, panicking on malformed input for
+/// the same reason `read_settings` does: a bad instrument spec means there's
+/// nothing sensible to hedge against.
+pub fn read_option_params(filename: &str) -> OptionParams {
+    let file = File::open(filename).expect("Failed to open option params file");
+    serde_json::from_reader(file).expect("Failed to parse option params file")
+}
+
+fn standard_normal_pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * PI).sqrt()
+}
+
+/// Abramowitz & Stegun approximation of the standard normal CDF, accurate to
+/// within 1.5e-7 — close enough for sizing a hedge ratio.
+fn standard_normal_cdf(x: f64) -> f64 {
+    let b1 = 0.319381530;
+    let b2 = -0.356563782;
+    let b3 = 1.781477937;
+    let b4 = -1.821255978;
+    let b5 = 1.330274429;
+    let p = 0.2316419;
+
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + p * x);
+    let poly = t * (b1 + t * (b2 + t * (b3 + t * (b4 + t * b5))));
+    let cdf = 1.0 - standard_normal_pdf(x) * poly;
+
+    0.5 + sign * (cdf - 0.5)
+}
+
+fn d1_d2(params: &OptionParams) -> (f64, f64) {
+    let OptionParams {
+        spot,
+        strike,
+        rate,
+        volatility,
+        time_to_expiry,
+        ..
+    } = *params;
+
+    let d1 = ((spot / strike).ln() + (rate + volatility * volatility / 2.0) * time_to_expiry)
+        / (volatility * time_to_expiry.sqrt());
+    let d2 = d1 - volatility * time_to_expiry.sqrt();
+
+    (d1, d2)
+}
+
+fn intrinsic_value(params: &OptionParams) -> f64 {
+    if params.is_call {
+        (params.spot - params.strike).max(0.0)
+    } else {
+        (params.strike - params.spot).max(0.0)
+    }
+}
+
+/// Black-Scholes price of a European option.
+pub fn price(params: &OptionParams) -> f64 {
+    if params.time_to_expiry == 0.0 {
+        return intrinsic_value(params);
+    }
+
+    let (d1, d2) = d1_d2(params);
+    let discounted_strike = params.strike * (-params.rate * params.time_to_expiry).exp();
+
+    if params.is_call {
+        params.spot * standard_normal_cdf(d1) - discounted_strike * standard_normal_cdf(d2)
+    } else {
+        discounted_strike * standard_normal_cdf(-d2) - params.spot * standard_normal_cdf(-d1)
+    }
+}
+
+/// Rate of change of the option price with respect to spot — the hedge ratio
+/// for sizing an offsetting position against `order_size`.
+pub fn delta(params: &OptionParams) -> f64 {
+    if params.time_to_expiry == 0.0 {
+        return if params.is_call {
+            if params.spot > params.strike { 1.0 } else { 0.0 }
+        } else if params.spot < params.strike {
+            -1.0
+        } else {
+            0.0
+        };
+    }
+
+    let (d1, _) = d1_d2(params);
+    if params.is_call {
+        standard_normal_cdf(d1)
+    } else {
+        standard_normal_cdf(d1) - 1.0
+    }
+}
+
+/// Rate of change of delta with respect to spot.
+pub fn gamma(params: &OptionParams) -> f64 {
+    if params.time_to_expiry == 0.0 {
+        return 0.0;
+    }
+
+    let (d1, _) = d1_d2(params);
+    standard_normal_pdf(d1) / (params.spot * params.volatility * params.time_to_expiry.sqrt())
+}
+
+/// Sensitivity of the option price to a unit change in volatility (1.0, i.e.
+/// 100 percentage points), not to a one-point move.
+pub fn vega(params: &OptionParams) -> f64 {
+    if params.time_to_expiry == 0.0 {
+        return 0.0;
+    }
+
+    let (d1, _) = d1_d2(params);
+    params.spot * standard_normal_pdf(d1) * params.time_to_expiry.sqrt()
+}
+
+/// Sensitivity of the option price to the passage of one year of time.
+pub fn theta(params: &OptionParams) -> f64 {
+    if params.time_to_expiry == 0.0 {
+        return 0.0;
+    }
+
+    let (d1, d2) = d1_d2(params);
+    let term1 = -(params.spot * standard_normal_pdf(d1) * params.volatility)
+        / (2.0 * params.time_to_expiry.sqrt());
+
+    if params.is_call {
+        term1
+            - params.rate
+                * params.strike
+                * (-params.rate * params.time_to_expiry).exp()
+                * standard_normal_cdf(d2)
+    } else {
+        term1
+            + params.rate
+                * params.strike
+                * (-params.rate * params.time_to_expiry).exp()
+                * standard_normal_cdf(-d2)
+    }
+}
+
+/// Cox-Ross-Rubinstein binomial tree price for an American-style option,
+/// allowing early exercise at every node.
+pub fn binomial_tree_price(params: &OptionParams, steps: usize) -> f64 {
+    if params.time_to_expiry == 0.0 {
+        return intrinsic_value(params);
+    }
+
+    let dt = params.time_to_expiry / steps as f64;
+    let u = (params.volatility * dt.sqrt()).exp();
+    let d = 1.0 / u;
+    let growth = (params.rate * dt).exp();
+    let p = (growth - d) / (u - d);
+
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "risk-neutral probability out of [0, 1]; check volatility/rate inputs"
+    );
+
+    let mut values: Vec<f64> = (0..=steps)
+        .map(|i| {
+            let spot_at_leaf = params.spot * u.powi((steps - i) as i32) * d.powi(i as i32);
+            let leaf = OptionParams {
+                spot: spot_at_leaf,
+                ..params.clone()
+            };
+            intrinsic_value(&leaf)
+        })
+        .collect();
+
+    for step in (0..steps).rev() {
+        for i in 0..=step {
+            let continuation = growth.recip() * (p * values[i] + (1.0 - p) * values[i + 1]);
+            let spot_at_node = params.spot * u.powi((step - i) as i32) * d.powi(i as i32);
+            let node = OptionParams {
+                spot: spot_at_node,
+                ..params.clone()
+            };
+            values[i] = continuation.max(intrinsic_value(&node));
+        }
+    }
+
+    values[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_the_money_call() -> OptionParams {
+        OptionParams {
+            spot: 100.0,
+            strike: 100.0,
+            rate: 0.05,
+            volatility: 0.2,
+            time_to_expiry: 1.0,
+            is_call: true,
+        }
+    }
+
+    #[test]
+    fn test_black_scholes_call_price_is_reasonable() {
+        let params = at_the_money_call();
+        let value = price(&params);
+        assert!((10.0..=11.0).contains(&value), "got {value}");
+    }
+
+    #[test]
+    fn test_call_delta_between_zero_and_one() {
+        let params = at_the_money_call();
+        let d = delta(&params);
+        assert!((0.0..=1.0).contains(&d), "got {d}");
+    }
+
+    #[test]
+    fn test_zero_time_to_expiry_returns_intrinsic_value() {
+        let mut params = at_the_money_call();
+        params.time_to_expiry = 0.0;
+        params.spot = 110.0;
+
+        assert_eq!(price(&params), 10.0);
+        assert_eq!(delta(&params), 1.0);
+    }
+
+    #[test]
+    fn test_binomial_tree_converges_near_black_scholes_for_european_payoff() {
+        let params = at_the_money_call();
+        let bs_price = price(&params);
+        let tree_price = binomial_tree_price(&params, 200);
+
+        assert!((bs_price - tree_price).abs() < 0.5, "bs={bs_price} tree={tree_price}");
+    }
+
+    #[test]
+    #[should_panic(expected = "risk-neutral probability out of [0, 1]")]
+    fn test_binomial_tree_panics_on_out_of_range_probability() {
+        // A rate this large relative to volatility drives the risk-neutral
+        // probability far outside [0, 1]; it must fail loudly rather than
+        // silently clamp and return a nonsense price.
+        let mut params = at_the_money_call();
+        params.rate = 10.0;
+        params.volatility = 0.001;
+
+        binomial_tree_price(&params, 2);
+    }
+}
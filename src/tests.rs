@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod test_helpers {
     use crate::helpers::{
-        convert_increment_to_precision, invert_side, read_settings, write_to_csv, SettingsFile, Side
+        convert_increment_to_precision, invert_side, read_binary, read_settings, write_to_binary,
+        write_to_csv, Compression, CurrencyCode, ExchangeCode, SettingsFile, Side, TradeRecord,
     };
     use rust_decimal::prelude::FromPrimitive;
     use rust_decimal::Decimal;
@@ -53,6 +54,9 @@ mod test_helpers {
             tp_percent: Default::default(),
             sl_percent: Default::default(),
             write_to_file: false,
+            compression: Compression::None,
+            max_log_bytes: 0,
+            log_candles: false,
         };
 
         to_writer_pretty(&File::create(filename).expect("Failed to create file"), &data)
@@ -72,4 +76,128 @@ mod test_helpers {
         assert_eq!(invert_side(Side::Sell), Side::Buy);
         assert_eq!(invert_side(Side::Buy), Side::Sell);
     }
+
+    #[test]
+    fn test_write_and_read_binary() {
+        let filename = "test_write_to_binary.bin";
+
+        let record = TradeRecord {
+            exchange: ExchangeCode::Coinbase,
+            base: CurrencyCode::Btc,
+            quote: CurrencyCode::Usd,
+            side: Side::Buy,
+            server_time: None,
+            time: 1_690_000_000_000_000_000,
+            price: 65000.5,
+            amount: 0.25,
+        };
+
+        write_to_binary(filename, record).expect("Failed to write binary record");
+
+        let records: Vec<TradeRecord> = read_binary(filename)
+            .expect("Failed to open binary log")
+            .collect();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0], record);
+        assert_eq!(records[0].server_time, None);
+
+        fs::remove_file(filename).expect("Failed to remove test file");
+    }
+
+    #[test]
+    fn test_binary_record_zero_server_time_decodes_to_none() {
+        let filename = "test_server_time_zero.bin";
+
+        let record = TradeRecord {
+            exchange: ExchangeCode::Binance,
+            base: CurrencyCode::Eth,
+            quote: CurrencyCode::Usdt,
+            side: Side::Sell,
+            server_time: Some(0),
+            time: 1_690_000_000_000_000_000,
+            price: 3200.25,
+            amount: 1.5,
+        };
+
+        write_to_binary(filename, record).expect("Failed to write binary record");
+
+        let decoded = read_binary(filename)
+            .expect("Failed to open binary log")
+            .next()
+            .expect("Expected one record");
+
+        assert_eq!(decoded.server_time, None);
+
+        fs::remove_file(filename).expect("Failed to remove test file");
+    }
+
+    #[test]
+    fn test_rotating_csv_writer_compresses_on_size_rotation() {
+        use crate::helpers::RotatingCsvWriter;
+
+        let filename = "test_rotating_writer.csv";
+        let mut writer = RotatingCsvWriter::new(filename, Compression::Gzip, 1);
+
+        writer.write(Decimal::from(1i64), Decimal::from(1i64), &Side::Buy, 1).unwrap();
+        writer.write(Decimal::from(2i64), Decimal::from(2i64), &Side::Sell, 2).unwrap();
+        writer.write(Decimal::from(3i64), Decimal::from(3i64), &Side::Buy, 3).unwrap();
+
+        let first_archive = format!("{filename}.1.gz");
+        let second_archive = format!("{filename}.2.gz");
+
+        // Each rotation must get its own archive name so a later rotation
+        // never clobbers an earlier one.
+        assert!(fs::metadata(&first_archive).is_ok());
+        assert!(fs::metadata(&second_archive).is_ok());
+        assert!(fs::metadata(filename).is_ok());
+
+        fs::remove_file(first_archive).expect("Failed to remove archived segment");
+        fs::remove_file(second_archive).expect("Failed to remove archived segment");
+        fs::remove_file(filename).expect("Failed to remove test file");
+    }
+
+    #[test]
+    fn test_rotating_csv_writer_rotates_without_compression() {
+        use crate::helpers::RotatingCsvWriter;
+
+        let filename = "test_rotating_writer_uncompressed.csv";
+        let mut writer = RotatingCsvWriter::new(filename, Compression::None, 1);
+
+        writer.write(Decimal::from(1i64), Decimal::from(1i64), &Side::Buy, 1).unwrap();
+        writer.write(Decimal::from(2i64), Decimal::from(2i64), &Side::Sell, 2).unwrap();
+
+        let archived = format!("{filename}.1.csv");
+
+        // Rotation must still close out the segment even with compression
+        // disabled, otherwise max_log_bytes is silently unenforced.
+        assert!(fs::metadata(&archived).is_ok());
+        assert!(fs::metadata(filename).is_ok());
+
+        let archived_contents = fs::read_to_string(&archived).expect("Failed to read archived segment");
+        assert!(archived_contents.contains('1'));
+
+        fs::remove_file(archived).expect("Failed to remove archived segment");
+        fs::remove_file(filename).expect("Failed to remove test file");
+    }
+
+    #[test]
+    fn test_read_compressed_csv_round_trips_gzip() {
+        use crate::helpers::{compress_to_gzip, read_compressed_csv};
+
+        let filename = "test_read_compressed.csv";
+        write_to_csv(filename, Decimal::from(42i64), Decimal::from(7i64), &Side::Buy, 1).unwrap();
+
+        let archived = format!("{filename}.gz");
+        compress_to_gzip(filename, &archived).expect("Failed to compress segment");
+
+        let mut rdr = read_compressed_csv(&archived).expect("Failed to open compressed CSV");
+        for result in rdr.records() {
+            let record = result.expect("Failed to read record");
+            assert_eq!(&record[1], "42");
+            assert_eq!(&record[2], "7");
+        }
+
+        fs::remove_file(archived).expect("Failed to remove archived segment");
+    }
 }
@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+
+use rust_decimal::Decimal;
+
+/// A single `(timestamp, value, weight)` entry held in a `WeightedMeanWindow`.
+struct Entry {
+    ts: u64,
+    value: Decimal,
+    weight: Decimal,
+}
+
+/// A sliding window over `(timestamp, value, weight)` entries that keeps a
+/// running weighted mean, evicting anything older than `now - window_ns` on
+/// every push. Feed it trade size as `weight` for a VWAP, or the time each
+/// value was live for a time-weighted mid-price.
+pub struct WeightedMeanWindow {
+    window_ns: u64,
+    entries: VecDeque<Entry>,
+    numerator: Decimal,
+    denominator: Decimal,
+}
+
+impl WeightedMeanWindow {
+    pub fn new(window_ns: u64) -> WeightedMeanWindow {
+        WeightedMeanWindow {
+            window_ns,
+            entries: VecDeque::new(),
+            numerator: Decimal::ZERO,
+            denominator: Decimal::ZERO,
+        }
+    }
+
+    /// Records a new `(ts, value, weight)` observation and evicts anything
+    /// that has fallen out of the lookback window as of `ts`.
+    pub fn push(&mut self, ts: u64, value: Decimal, weight: Decimal) {
+        self.numerator += value * weight;
+        self.denominator += weight;
+        self.entries.push_back(Entry { ts, value, weight });
+
+        self.purge_before(ts.saturating_sub(self.window_ns));
+    }
+
+    /// Evicts all entries with `ts < cutoff`, updating the running sums
+    /// incrementally rather than recomputing them from the remaining entries.
+    pub fn purge_before(&mut self, cutoff: u64) {
+        while let Some(front) = self.entries.front() {
+            if front.ts >= cutoff {
+                break;
+            }
+            let evicted = self.entries.pop_front().unwrap();
+            self.numerator -= evicted.value * evicted.weight;
+            self.denominator -= evicted.weight;
+        }
+    }
+
+    /// Returns the current weighted mean, or `None` if the window is empty
+    /// or every remaining weight is zero.
+    pub fn mean(&self) -> Option<Decimal> {
+        if self.denominator.is_zero() {
+            None
+        } else {
+            Some(self.numerator / self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::prelude::FromPrimitive;
+
+    fn dec(value: f64) -> Decimal {
+        Decimal::from_f64(value).unwrap()
+    }
+
+    #[test]
+    fn test_weighted_mean_is_volume_weighted() {
+        let mut window = WeightedMeanWindow::new(10_000);
+
+        window.push(0, dec(10.0), dec(1.0));
+        window.push(1, dec(20.0), dec(3.0));
+
+        // (10*1 + 20*3) / (1+3) = 70/4 = 17.5
+        assert_eq!(window.mean(), Some(dec(17.5)));
+    }
+
+    #[test]
+    fn test_weighted_mean_evicts_stale_entries() {
+        let mut window = WeightedMeanWindow::new(10);
+
+        window.push(0, dec(10.0), dec(1.0));
+        window.push(20, dec(30.0), dec(1.0));
+
+        assert_eq!(window.mean(), Some(dec(30.0)));
+    }
+
+    #[test]
+    fn test_weighted_mean_empty_window_is_none() {
+        let window = WeightedMeanWindow::new(10_000);
+        assert_eq!(window.mean(), None);
+    }
+}